@@ -1,12 +1,14 @@
 use rustc_hash::{FxHashSet, FxHashMap};
 
-use ruff_python_ast::imports::{ImportMap, ModuleMapping};
+use ruff_python_ast::imports::ImportMap;
 use ruff_python_semantic::analyze::function_type;
 use ruff_python_semantic::analyze::function_type::FunctionType;
 use ruff_python_semantic::scope::{FunctionDef, ScopeKind};
 
 use crate::checkers::ast::Checker;
 
+use super::import_graph::ImportGraph;
+
 pub fn in_dunder_init(checker: &Checker) -> bool {
     let scope = checker.ctx.scope();
     let ScopeKind::Function(FunctionDef {
@@ -39,22 +41,16 @@ pub fn in_dunder_init(checker: &Checker) -> bool {
     true
 }
 
-#[derive(Default)]
 pub struct CyclicImportHelper<'a> {
     pub cycles: FxHashMap<u32, FxHashSet<Vec<u32>>>,
-    pub module_mapping: ModuleMapping<'a>,
+    pub graph: ImportGraph<'a>,
 }
 
 impl<'a> CyclicImportHelper<'a> {
     pub fn new(import_map: &'a ImportMap) -> Self {
-        let mut module_mapping = ModuleMapping::new();
-        import_map.module_to_imports.keys().for_each(|module| {
-            module_mapping.insert(module);
-        });
-
         Self {
             cycles: FxHashMap::default(),
-            module_mapping,
+            graph: ImportGraph::new(import_map),
         }
     }
 }
@@ -0,0 +1,240 @@
+//! A reusable, interned-id view over a project's import graph.
+//!
+//! This started out as the ad-hoc machinery baked directly into the
+//! `cyclic-import` checker; it's pulled out here so any rule that needs to
+//! reason about module structure (cyclic imports, layering boundaries, and
+//! whatever comes next) can share one graph instead of re-deriving it.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+
+use ruff_python_ast::imports::{ImportMap, ModuleImport, ModuleMapping};
+
+/// The bookkeeping Tarjan's algorithm needs for a single node: the order in
+/// which it was first discovered, and the lowest discovery order reachable
+/// from it via the current DFS tree plus at most one back-edge.
+#[derive(Clone, Copy)]
+struct NodeState {
+    index: u32,
+    lowlink: u32,
+}
+
+/// One frame of the explicit work stack that replaces recursion in
+/// [`ImportGraph::strongly_connected_components`]. `next_import` tracks how
+/// far we've iterated through `node`'s imports so we can resume where we
+/// left off after "recursing" into a successor.
+struct Frame {
+    node: u32,
+    next_import: usize,
+}
+
+/// Nodes are interned modules (see [`ModuleMapping`]); edges are the
+/// [`ModuleImport`]s that produced them, so a query can always recover the
+/// `TextRange` of the import statement that crosses a graph boundary.
+pub struct ImportGraph<'a> {
+    module_mapping: ModuleMapping<'a>,
+    adjacency: Vec<Vec<u32>>,
+    imports: &'a FxHashMap<String, Vec<ModuleImport>>,
+}
+
+impl<'a> ImportGraph<'a> {
+    pub fn new(import_map: &'a ImportMap) -> Self {
+        let mut module_mapping = ModuleMapping::new();
+        // Intern every module that appears as either the source or the
+        // target of an import, since a query can pass through a module that
+        // is only ever imported, never itself the key of an entry.
+        for (module, imports) in &import_map.module_to_imports {
+            module_mapping.insert(module);
+            for import in imports {
+                module_mapping.insert(&import.module);
+            }
+        }
+
+        let mut adjacency = vec![Vec::new(); module_mapping.len()];
+        for (module, imports) in &import_map.module_to_imports {
+            let module_id = module_mapping.insert(module);
+            adjacency[module_id as usize] = imports
+                .iter()
+                .map(|import| module_mapping.insert(&import.module))
+                .collect();
+        }
+
+        Self {
+            module_mapping,
+            adjacency,
+            imports: &import_map.module_to_imports,
+        }
+    }
+
+    pub fn module_id(&self, module: &str) -> Option<u32> {
+        self.module_mapping.get(module)
+    }
+
+    pub fn module_name(&self, id: u32) -> Option<&'a str> {
+        self.module_mapping.get_name(id)
+    }
+
+    pub fn successors(&self, module: u32) -> &[u32] {
+        &self.adjacency[module as usize]
+    }
+
+    /// The `ModuleImport` edges that `module` declares, in source order, so
+    /// callers can recover the `TextRange` of a specific import statement.
+    pub fn edges(&self, module: u32) -> &'a [ModuleImport] {
+        self.module_name(module)
+            .and_then(|name| self.imports.get(name))
+            .map_or(&[][..], Vec::as_slice)
+    }
+
+    /// Every module transitively reachable from `start`, `start` included.
+    pub fn reachable_from(&self, start: u32) -> FxHashSet<u32> {
+        let mut seen = FxHashSet::default();
+        let mut stack = vec![start];
+        seen.insert(start);
+        while let Some(node) = stack.pop() {
+            for &next in self.successors(node) {
+                if seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    /// The shortest import path from `from` to `to` (inclusive of both
+    /// endpoints), or `None` if `to` isn't reachable from `from`.
+    pub fn shortest_path(&self, from: u32, to: u32) -> Option<Vec<u32>> {
+        if from == to {
+            return Some(vec![from]);
+        }
+
+        let mut predecessor: FxHashMap<u32, u32> = FxHashMap::default();
+        predecessor.insert(from, from);
+        let mut queue = VecDeque::new();
+        queue.push_back(from);
+
+        while let Some(node) = queue.pop_front() {
+            for &next in self.successors(node) {
+                if predecessor.contains_key(&next) {
+                    continue;
+                }
+                predecessor.insert(next, node);
+                if next == to {
+                    let mut path = vec![to];
+                    let mut current = to;
+                    while current != from {
+                        current = predecessor[&current];
+                        path.push(current);
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(next);
+            }
+        }
+        None
+    }
+
+    /// Find every strongly-connected component reachable from `start`, using
+    /// an iterative version of Tarjan's algorithm (graphs of real-world
+    /// Python packages can be deep enough to blow the native stack with a
+    /// recursive DFS).
+    pub fn strongly_connected_components(&self, start: u32) -> (FxHashSet<u32>, Vec<Vec<u32>>) {
+        let mut index = 0u32;
+        let mut node_states: FxHashMap<u32, NodeState> = FxHashMap::default();
+        let mut on_stack: FxHashSet<u32> = FxHashSet::default();
+        let mut stack: Vec<u32> = Vec::new();
+        let mut components: Vec<Vec<u32>> = Vec::new();
+
+        let mut work: Vec<Frame> = Vec::new();
+        Self::visit(start, &mut index, &mut node_states, &mut on_stack, &mut stack, &mut work);
+
+        while let Some(frame) = work.last_mut() {
+            let node = frame.node;
+            let successors = self.successors(node);
+
+            if frame.next_import >= successors.len() {
+                self.finish_node(node, &mut work, &mut node_states, &mut on_stack, &mut stack, &mut components);
+                continue;
+            }
+
+            let successor = successors[frame.next_import];
+            frame.next_import += 1;
+
+            if let Some(&successor_state) = node_states.get(&successor) {
+                if on_stack.contains(&successor) {
+                    let node_lowlink = node_states[&node].lowlink;
+                    if successor_state.index < node_lowlink {
+                        node_states.get_mut(&node).unwrap().lowlink = successor_state.index;
+                    }
+                }
+            } else {
+                Self::visit(
+                    successor,
+                    &mut index,
+                    &mut node_states,
+                    &mut on_stack,
+                    &mut stack,
+                    &mut work,
+                );
+            }
+        }
+
+        (node_states.into_keys().collect(), components)
+    }
+
+    fn visit(
+        node: u32,
+        index: &mut u32,
+        node_states: &mut FxHashMap<u32, NodeState>,
+        on_stack: &mut FxHashSet<u32>,
+        stack: &mut Vec<u32>,
+        work: &mut Vec<Frame>,
+    ) {
+        node_states.insert(
+            node,
+            NodeState {
+                index: *index,
+                lowlink: *index,
+            },
+        );
+        *index += 1;
+        stack.push(node);
+        on_stack.insert(node);
+        work.push(Frame {
+            node,
+            next_import: 0,
+        });
+    }
+
+    fn finish_node(
+        &self,
+        node: u32,
+        work: &mut Vec<Frame>,
+        node_states: &mut FxHashMap<u32, NodeState>,
+        on_stack: &mut FxHashSet<u32>,
+        stack: &mut Vec<u32>,
+        components: &mut Vec<Vec<u32>>,
+    ) {
+        work.pop();
+        let node_state = node_states[&node];
+        if node_state.lowlink == node_state.index {
+            let mut component = Vec::new();
+            while let Some(top) = stack.pop() {
+                on_stack.remove(&top);
+                component.push(top);
+                if top == node {
+                    break;
+                }
+            }
+            components.push(component);
+        }
+        if let Some(parent) = work.last().map(|frame| frame.node) {
+            let node_lowlink = node_state.lowlink;
+            let parent_state = node_states.get_mut(&parent).unwrap();
+            if node_lowlink < parent_state.lowlink {
+                parent_state.lowlink = node_lowlink;
+            }
+        }
+    }
+}
@@ -2,11 +2,17 @@
 use std::path::Path;
 
 use rustc_hash::{FxHashMap, FxHashSet};
+use rustpython_parser::ast::{Constant, Expr, ExprKind, Stmt, StmtKind, Suite};
 
-use ruff_diagnostics::{Diagnostic, Violation};
+use ruff_diagnostics::{AutofixKind, Diagnostic, Edit, Fix, Violation};
 use ruff_macros::{derive_message_formats, violation};
 use ruff_python_ast::helpers::to_module_path;
 use ruff_python_ast::imports::ModuleImport;
+use ruff_python_ast::source_code::{Locator, Stylist};
+
+use super::super::helpers::CyclicImportHelper;
+use super::super::import_graph::ImportGraph;
+use super::use_placement::{find_use_placement, UsePlacement};
 
 #[violation]
 pub struct CyclicImport {
@@ -14,19 +20,25 @@ pub struct CyclicImport {
 }
 
 impl Violation for CyclicImport {
+    const AUTOFIX: AutofixKind = AutofixKind::Sometimes;
+
     #[derive_message_formats]
     fn message(&self) -> String {
         format!("Cyclic import ({}) (cyclic-import)", self.cycle)
     }
+
+    fn autofix_title(&self) -> Option<String> {
+        Some("Defer the import to break the cycle".to_string())
+    }
 }
 
-struct VisitedAndCycles<'a> {
-    fully_visited: FxHashSet<&'a str>,
-    cycles: Option<FxHashSet<Vec<&'a str>>>,
+struct VisitedAndCycles {
+    fully_visited: FxHashSet<u32>,
+    cycles: Option<FxHashSet<Vec<u32>>>,
 }
 
-impl<'a> VisitedAndCycles<'a> {
-    fn new(fully_visited: FxHashSet<&'a str>, cycles: FxHashSet<Vec<&'a str>>) -> Self {
+impl VisitedAndCycles {
+    fn new(fully_visited: FxHashSet<u32>, cycles: FxHashSet<Vec<u32>>) -> Self {
         if cycles.is_empty() {
             Self {
                 fully_visited,
@@ -41,62 +53,117 @@ impl<'a> VisitedAndCycles<'a> {
     }
 }
 
+/// Walks the shared [`ImportGraph`] to find cyclic-import groups, over
+/// interned module ids rather than `&str` so neither the SCC pass nor the
+/// cycle-path recovery pass hash a string; only the final diagnostic
+/// formatting maps ids back to names.
 struct CyclicImportChecker<'a> {
-    imports: &'a FxHashMap<String, Vec<ModuleImport>>,
+    graph: &'a ImportGraph<'a>,
 }
 
 impl<'a> CyclicImportChecker<'a> {
-    fn has_cycles(&self, name: &'a str) -> VisitedAndCycles<'a> {
-        // we check before hand that the name is in the imports, ergo it will be in the module mapping and thus this unwrap is safe
-        let mut stack: Vec<&str> = vec![name];
-        let mut fully_visited: FxHashSet<&str> = FxHashSet::default();
-        let mut cycles: FxHashSet<Vec<&str>> = FxHashSet::default();
-        self.has_cycles_helper(
-            name,
-            &mut stack,
-            &mut cycles,
-            &mut fully_visited,
-            // 0,
-        );
+    fn has_cycles(&self, start: u32) -> VisitedAndCycles {
+        let (fully_visited, components) = self.graph.strongly_connected_components(start);
+
+        let mut cycles: FxHashSet<Vec<u32>> = FxHashSet::default();
+        for component in &components {
+            if component.len() > 1 {
+                // A genuine cycle: recover one concrete ordered path through
+                // it, rooted at the module we're actually checking (`start`)
+                // whenever it's a member, so the path we report always
+                // contains the module being linted.
+                cycles.insert(self.recover_cycle(start, component));
+            } else if self.imports_self(component[0]) {
+                // A single-node component that imports itself is also a cycle.
+                cycles.insert(component.clone());
+            }
+        }
+
         VisitedAndCycles::new(fully_visited, cycles)
     }
 
-    fn has_cycles_helper(
-        &self,
-        name: &'a str,
-        stack: &mut Vec<&'a str>,
-        cycles: &mut FxHashSet<Vec<&'a str>>,
-        fully_visited: &mut FxHashSet<&'a str>,
-        // level: usize,
-    ) {
-        if let Some(imports) = self.imports.get(name) {
-            // let tabs = "\t".repeat(level);
-            // debug!("{tabs}{name}");
-            for import in imports.iter() {
-                // debug!("{tabs}\timport: {}", import.module);
-                if let Some(idx) = stack.iter().position(|s| s == &import.module) {
-                    // debug!("{tabs}\t\t cycles: {:?}", stack[idx..].to_vec());
-                    // when the length is 1 and the only item is the import we're looking at
-                    // then we're importing self, could we report this so we don't have to
-                    // do this again for import-self W0406?
-                    if stack[idx..].len() == 1 && stack[idx] == name {
-                        continue;
+    fn imports_self(&self, module: u32) -> bool {
+        self.graph.successors(module).contains(&module)
+    }
+
+    /// Recover one concrete, ordered cycle through `component` via a DFS
+    /// restricted to the component's own members, so the diagnostic has a
+    /// real path to show rather than just the (unordered) set of modules
+    /// involved. Rooted at `queried` when it's part of the component, since
+    /// a path rooted anywhere else isn't guaranteed to pass through it at
+    /// all (an SCC can contain cycles that skip any given one of its
+    /// members).
+    fn recover_cycle(&self, queried: u32, component: &[u32]) -> Vec<u32> {
+        let allowed: FxHashSet<u32> = component.iter().copied().collect();
+        let root = if allowed.contains(&queried) {
+            queried
+        } else {
+            component[0]
+        };
+        self.recover_cycle_from(root, &allowed)
+    }
+
+    /// Iterative (explicit work stack) DFS for a path from `root` back to
+    /// itself that stays within `allowed`, so a single strongly-connected
+    /// component spanning most of a large package graph can't blow the
+    /// native stack the way a recursive DFS would.
+    fn recover_cycle_from(&self, root: u32, allowed: &FxHashSet<u32>) -> Vec<u32> {
+        struct Frame {
+            node: u32,
+            next_successor: usize,
+        }
+
+        let mut path = vec![root];
+        let mut on_path: FxHashSet<u32> = FxHashSet::default();
+        on_path.insert(root);
+        let mut work = vec![Frame {
+            node: root,
+            next_successor: 0,
+        }];
+
+        while let Some(frame) = work.last_mut() {
+            let node = frame.node;
+            let successors = self.graph.successors(node);
+
+            let mut advance_to = None;
+            while frame.next_successor < successors.len() {
+                let candidate = successors[frame.next_successor];
+                frame.next_successor += 1;
+                if !allowed.contains(&candidate) {
+                    continue;
+                }
+                if candidate == root {
+                    return path;
+                }
+                if !on_path.contains(&candidate) {
+                    advance_to = Some(candidate);
+                    break;
+                }
+            }
+
+            match advance_to {
+                Some(next) => {
+                    on_path.insert(next);
+                    path.push(next);
+                    work.push(Frame {
+                        node: next,
+                        next_successor: 0,
+                    });
+                }
+                None => {
+                    work.pop();
+                    if work.is_empty() {
+                        // Backtracked out of `root` itself without finding a
+                        // cycle; leave `path` as just `[root]`.
+                        break;
                     }
-                    cycles.insert(stack[idx..].to_vec());
-                } else {
-                    stack.push(&import.module);
-                    self.has_cycles_helper(
-                        &import.module,
-                        stack,
-                        cycles,
-                        fully_visited,
-                        // level + 1,
-                    );
-                    stack.pop();
+                    on_path.remove(&node);
+                    path.pop();
                 }
             }
         }
-        fully_visited.insert(name);
+
+        path
     }
 }
 
@@ -105,7 +172,10 @@ pub fn cyclic_import<'a>(
     path: &Path,
     package: Option<&Path>,
     imports: &'a FxHashMap<String, Vec<ModuleImport>>,
-    cycles: &mut FxHashMap<&'a str, FxHashSet<Vec<&'a str>>>,
+    helper: &mut CyclicImportHelper<'a>,
+    python_ast: &Suite,
+    locator: &Locator,
+    stylist: &Stylist,
 ) -> Option<Vec<Diagnostic>> {
     let Some(package) = package else {
         return None;
@@ -115,77 +185,109 @@ pub fn cyclic_import<'a>(
     };
     let module_name = module_name.join(".");
     // if the module name isn't in the import map, it can't possibly have cycles
-    // this also allows us to use `unwrap` whenever we use methods on the `ModuleMapping`
-    // as any modules as part of cycles are guaranteed to be in the `ModuleMapping`
     // debug!("Checking module {module_name}");
     let Some((module_name, _)) = imports.get_key_value(&module_name as &str) else {
         return None;
     };
-    if let Some(existing_cycles) = cycles.get(module_name as &str) {
+    // every module in `imports` was interned while `CyclicImportHelper` was built,
+    // so this lookup is guaranteed to succeed
+    let module_id = helper.graph.module_id(module_name).unwrap();
+
+    if let Some(existing_cycles) = helper.cycles.get(&module_id) {
         if existing_cycles.is_empty() {
             return None;
         }
         // debug!("Existing cycles: {existing_cycles:#?}");
+        // Cached cycles were rotated to start at `module_id` before being
+        // stored (see the caching loop below), so `cycle[1]` is always the
+        // next hop `module_id` takes around the cycle - the same edge a live
+        // computation would have pivoted on, which lets us offer the same
+        // fix here instead of only on whichever module's check happens to
+        // run the live `has_cycles` computation for a given cycle.
         Some(
             existing_cycles
                 .iter()
                 .map(|cycle| {
-                    Diagnostic::new(
+                    let next_hop = cycle.get(1).copied().unwrap_or(module_id);
+                    let next_hop_name = helper.graph.module_name(next_hop).unwrap();
+                    let edge = imports[module_name]
+                        .iter()
+                        .find(|m| m.module == next_hop_name)
+                        .unwrap_or(&imports[module_name][0]);
+                    let mut diagnostic = Diagnostic::new(
                         CyclicImport {
-                            cycle: cycle.join(" -> "),
+                            cycle: format_cycle(&helper.graph, cycle),
                         },
-                        (&imports[module_name][0]).into(),
-                    )
+                        edge.into(),
+                    );
+                    if let Some(fix) = generate_fix(python_ast, locator, stylist, edge) {
+                        diagnostic.set_fix(fix);
+                    }
+                    diagnostic
                 })
                 .collect::<Vec<Diagnostic>>(),
         )
     } else {
-        let cyclic_import_checker = CyclicImportChecker { imports };
+        let cyclic_import_checker = CyclicImportChecker {
+            graph: &helper.graph,
+        };
         let VisitedAndCycles {
             fully_visited: mut visited,
             cycles: new_cycles,
-        } = cyclic_import_checker.has_cycles(module_name);
+        } = cyclic_import_checker.has_cycles(module_id);
         // we'll always have new visited stuff if we have
         let mut out_vec: Vec<Diagnostic> = Vec::new();
         if let Some(new_cycles) = new_cycles {
             // debug!("New cycles {new_cycles:#?}");
             for new_cycle in &new_cycles {
-                if let [first, the_rest @ ..] = &new_cycle[..] {
-                    if first == module_name {
-                        out_vec.push(Diagnostic::new(
-                            CyclicImport {
-                                cycle: new_cycle.join(" -> "),
-                            },
-                            imports[module_name]
-                                .iter()
-                                .find(|m| m.module == the_rest[0])
-                                .unwrap()
-                                .into(),
-                        ));
+                // `recover_cycle` roots a cycle at `module_id` whenever
+                // `module_id` is a member of its component; cycles of other
+                // components reachable from it are not reported here. The
+                // rotation keeps `rotated[1]` the module that `module_id`
+                // imports next around the cycle.
+                if let Some(pos) = new_cycle.iter().position(|&m| m == module_id) {
+                    let rotated = new_cycle[pos..]
+                        .iter()
+                        .chain(new_cycle[..pos].iter())
+                        .copied()
+                        .collect::<Vec<_>>();
+                    let next_hop = rotated.get(1).copied().unwrap_or(module_id);
+                    let next_hop_name = helper.graph.module_name(next_hop).unwrap();
+                    let edge = imports[module_name]
+                        .iter()
+                        .find(|m| m.module == next_hop_name)
+                        .unwrap_or(&imports[module_name][0]);
+                    let mut diagnostic = Diagnostic::new(
+                        CyclicImport {
+                            cycle: format_cycle(&helper.graph, &rotated),
+                        },
+                        edge.into(),
+                    );
+                    if let Some(fix) = generate_fix(python_ast, locator, stylist, edge) {
+                        diagnostic.set_fix(fix);
                     }
+                    out_vec.push(diagnostic);
                 }
-                for involved_module in new_cycle.iter() {
+                for &involved_module in new_cycle.iter() {
                     // we re-order the cycles for the modules involved here
-                    let pos = new_cycle.iter().position(|s| s == involved_module).unwrap();
+                    let pos = new_cycle.iter().position(|s| *s == involved_module).unwrap();
                     let cycle_to_insert = new_cycle[pos..]
                         .iter()
                         .chain(new_cycle[..pos].iter())
-                        .map(std::clone::Clone::clone)
+                        .copied()
                         .collect::<Vec<_>>();
-                    if let Some(existing) = cycles.get_mut(involved_module) {
-                        existing.insert(cycle_to_insert);
-                    } else {
-                        let mut new_set = FxHashSet::default();
-                        new_set.insert(cycle_to_insert);
-                        cycles.insert(*involved_module, new_set);
-                    }
-                    visited.remove(involved_module);
+                    helper
+                        .cycles
+                        .entry(involved_module)
+                        .or_default()
+                        .insert(cycle_to_insert);
+                    visited.remove(&involved_module);
                 }
             }
         }
         // process the visited nodes which don't have cycles
         for visited_module in visited {
-            cycles.insert(visited_module, FxHashSet::default());
+            helper.cycles.insert(visited_module, FxHashSet::default());
         }
         if out_vec.is_empty() {
             None
@@ -195,13 +297,219 @@ pub fn cyclic_import<'a>(
     }
 }
 
+fn format_cycle(graph: &ImportGraph, cycle: &[u32]) -> String {
+    cycle
+        .iter()
+        .map(|id| graph.module_name(*id).unwrap())
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// Try to build a fix that breaks the cycle reported for `edge` by deferring
+/// its import out of module scope: into `TYPE_CHECKING` if every reference
+/// to it is a type-only one, or into the function(s) that use it at runtime
+/// otherwise. Returns `None` if the import can't be safely moved (e.g. it's
+/// also used at module scope), in which case the violation stays report-only.
+fn generate_fix(
+    python_ast: &Suite,
+    locator: &Locator,
+    stylist: &Stylist,
+    edge: &ModuleImport,
+) -> Option<Fix> {
+    let import_stmt = find_import_stmt(python_ast, edge)?;
+    let bound_name = imported_name(import_stmt, &edge.module);
+
+    match find_use_placement(python_ast, bound_name) {
+        UsePlacement::TypeCheckingOnly(annotations) => {
+            build_type_checking_fix(python_ast, locator, stylist, import_stmt, &annotations)
+        }
+        UsePlacement::FunctionLocal(functions) if !functions.is_empty() => {
+            build_function_local_fix(locator, stylist, import_stmt, &functions)
+        }
+        UsePlacement::FunctionLocal(_) | UsePlacement::Unmovable => None,
+    }
+}
+
+/// The name `edge`'s import statement binds in the importing module's
+/// namespace.
+///
+/// For `import x.y.z`, Python binds only the top-level package `x` (never
+/// `z`) unless an alias is given, so we read the actual statement rather
+/// than guessing from the dotted path. For `from x import y`, this is
+/// `y`'s own alias if there is one, or `y` itself - this is still a
+/// heuristic when a single `from` statement imports multiple names, since
+/// `edge` only gives us the resolved module path, not which alias in the
+/// statement produced it.
+fn imported_name<'a>(import_stmt: &'a Stmt, module: &'a str) -> &'a str {
+    match &import_stmt.node {
+        StmtKind::Import { names } => names
+            .iter()
+            .find(|alias| alias.node.name == module)
+            .and_then(|alias| alias.node.asname.as_deref())
+            .unwrap_or_else(|| module.split('.').next().unwrap_or(module)),
+        StmtKind::ImportFrom { names, .. } => names
+            .iter()
+            .find(|alias| module.ends_with(alias.node.name.as_str()))
+            .and_then(|alias| alias.node.asname.as_deref())
+            .unwrap_or_else(|| module.rsplit('.').next().unwrap_or(module)),
+        _ => module.rsplit('.').next().unwrap_or(module),
+    }
+}
+
+fn find_import_stmt<'a>(suite: &'a Suite, edge: &ModuleImport) -> Option<&'a Stmt> {
+    let edge_range = edge.range();
+    suite.iter().find(|stmt| {
+        matches!(stmt.node, StmtKind::Import { .. } | StmtKind::ImportFrom { .. })
+            && stmt.range() == edge_range
+    })
+}
+
+fn has_type_checking_import(suite: &Suite) -> bool {
+    suite.iter().any(|stmt| {
+        matches!(
+            &stmt.node,
+            StmtKind::ImportFrom { module: Some(module), names, .. }
+                if module.as_str() == "typing"
+                    && names.iter().any(|alias| alias.node.name == "TYPE_CHECKING")
+        )
+    })
+}
+
+fn has_future_annotations_import(suite: &Suite) -> bool {
+    suite.iter().any(|stmt| {
+        matches!(
+            &stmt.node,
+            StmtKind::ImportFrom { module: Some(module), names, .. }
+                if module.as_str() == "__future__"
+                    && names.iter().any(|alias| alias.node.name == "annotations")
+        )
+    })
+}
+
+fn is_string_literal(expr: &Expr) -> bool {
+    matches!(&expr.node, ExprKind::Constant { value: Constant::Str(_), .. })
+}
+
+/// Move `import_stmt` into an `if TYPE_CHECKING:` block. Since annotations
+/// are evaluated eagerly at function-definition time unless `from
+/// __future__ import annotations` is in effect, every annotation that
+/// references the deferred name also has to become a quoted forward
+/// reference, or the fix would trade a cyclic import for a `NameError`.
+fn build_type_checking_fix(
+    python_ast: &Suite,
+    locator: &Locator,
+    stylist: &Stylist,
+    import_stmt: &Stmt,
+    annotations: &[&Expr],
+) -> Option<Fix> {
+    let indent = stylist.indentation();
+    let line_ending = stylist.line_ending().as_str();
+    let original = locator.slice(import_stmt.range());
+
+    let mut content = String::new();
+    if !has_type_checking_import(python_ast) {
+        content.push_str("from typing import TYPE_CHECKING");
+        content.push_str(line_ending);
+    }
+    content.push_str("if TYPE_CHECKING:");
+    content.push_str(line_ending);
+    content.push_str(indent.as_str());
+    content.push_str(original);
+
+    let import_edit = Edit::range_replacement(content, import_stmt.range());
+
+    if has_future_annotations_import(python_ast) {
+        return Some(Fix::unsafe_edit(import_edit));
+    }
+
+    let quote_edits = annotations
+        .iter()
+        .filter(|annotation| !is_string_literal(annotation))
+        .map(|annotation| {
+            let quoted = format!("\"{}\"", locator.slice(annotation.range()));
+            Edit::range_replacement(quoted, annotation.range())
+        })
+        .collect::<Vec<_>>();
+
+    Some(Fix::unsafe_edits(import_edit, quote_edits))
+}
+
+fn build_function_local_fix(
+    locator: &Locator,
+    stylist: &Stylist,
+    import_stmt: &Stmt,
+    functions: &[&Stmt],
+) -> Option<Fix> {
+    let original = locator.slice(import_stmt.range());
+    let line_ending = stylist.line_ending().as_str();
+
+    let mut inserts = Vec::new();
+    for function in functions {
+        let StmtKind::FunctionDef { body, .. } | StmtKind::AsyncFunctionDef { body, .. } =
+            &function.node
+        else {
+            continue;
+        };
+        let Some(insert_before) = body.iter().find(|stmt| !is_docstring_stmt(stmt)) else {
+            continue;
+        };
+        let indent = leading_indent(locator, insert_before);
+        let content = format!("{original}{line_ending}{indent}");
+        inserts.push(Edit::insertion(content, insert_before.range().start()));
+    }
+
+    if inserts.is_empty() {
+        return None;
+    }
+    Some(Fix::unsafe_edits(
+        Edit::range_deletion(import_stmt.range()),
+        inserts,
+    ))
+}
+
+fn is_docstring_stmt(stmt: &Stmt) -> bool {
+    matches!(
+        &stmt.node,
+        StmtKind::Expr { value } if matches!(
+            &value.node,
+            rustpython_parser::ast::ExprKind::Constant {
+                value: rustpython_parser::ast::Constant::Str(_),
+                ..
+            }
+        )
+    )
+}
+
+fn leading_indent(locator: &Locator, stmt: &Stmt) -> String {
+    let line_start = locator.line_start(stmt.range().start());
+    locator
+        .slice(ruff_text_size::TextRange::new(line_start, stmt.range().start()))
+        .to_string()
+}
+
 #[cfg(test)]
 mod tests {
+    use rustpython_parser::{lexer, parser};
+
     use ruff_python_ast::imports::ImportMap;
     use ruff_text_size::{TextRange, TextSize};
 
     use super::*;
 
+    fn helper_for(import_map: &ImportMap) -> CyclicImportHelper {
+        CyclicImportHelper::new(import_map)
+    }
+
+    /// Parse `source` and return the pieces [`cyclic_import`] needs to build
+    /// a fix: its AST, a [`Locator`], and a [`Stylist`].
+    fn parse(source: &str) -> (Suite, Locator, Stylist) {
+        let python_ast = parser::parse_program(source, "<filename>").unwrap();
+        let locator = Locator::new(source);
+        let tokens: Vec<_> = lexer::make_tokenizer(source).collect();
+        let stylist = Stylist::from_tokens(&tokens, &locator);
+        (python_ast, locator, stylist)
+    }
+
     #[test]
     fn cyclic_import_unrelated_module_not_traversed() {
         let mut map = FxHashMap::default();
@@ -215,22 +523,24 @@ mod tests {
         map.insert(a.module.clone(), vec![]);
         map.insert(b.module, vec![a.clone()]);
         let import_map = ImportMap::new(map);
+        let helper = helper_for(&import_map);
         let cyclic_checker = CyclicImportChecker {
-            imports: &import_map.module_to_imports,
+            graph: &helper.graph,
         };
 
+        let a_id = helper.graph.module_id(&a.module).unwrap();
         let VisitedAndCycles {
             fully_visited: visited,
             cycles,
-        } = cyclic_checker.has_cycles(&a.module);
-        let mut check_visited: FxHashSet<&str> = FxHashSet::default();
-        check_visited.insert(&a.module);
+        } = cyclic_checker.has_cycles(a_id);
+        let mut check_visited: FxHashSet<u32> = FxHashSet::default();
+        check_visited.insert(a_id);
         assert_eq!(visited, check_visited);
         assert!(cycles.is_none());
     }
 
     #[test]
-    fn cyclic_import_multiple_cycles() {
+    fn cyclic_import_multiple_cycles_collapse_to_one_component() {
         let mut map = FxHashMap::default();
         let size1 = TextSize::from(1);
         let size2 = TextSize::from(2);
@@ -251,30 +561,73 @@ mod tests {
         map.insert(c.module.clone(), vec![b.clone(), d.clone()]);
         map.insert(d.module.clone(), vec![a.clone()]);
         let import_map = ImportMap::new(map);
+        let helper = helper_for(&import_map);
         let cyclic_checker = CyclicImportChecker {
-            imports: &import_map.module_to_imports,
+            graph: &helper.graph,
         };
 
+        let a_id = helper.graph.module_id(&a.module).unwrap();
         let VisitedAndCycles {
             fully_visited: visited,
             cycles,
-        } = cyclic_checker.has_cycles(&a.module);
+        } = cyclic_checker.has_cycles(a_id);
 
-        let mut check_visited: FxHashSet<&str> = FxHashSet::default();
-        check_visited.insert(&a.module);
-        check_visited.insert(&b.module);
-        check_visited.insert(&c.module);
-        check_visited.insert(&d.module);
+        let mut check_visited: FxHashSet<u32> = FxHashSet::default();
+        for module in [&a.module, &b.module, &c.module, &d.module] {
+            check_visited.insert(helper.graph.module_id(module).unwrap());
+        }
         assert_eq!(visited, check_visited);
 
-        let mut check_cycles: FxHashSet<Vec<&str>> = FxHashSet::default();
-        check_cycles.insert(vec![&a.module, &b.module, &c.module, &d.module]);
-        check_cycles.insert(vec![&a.module, &c.module, &b.module, &d.module]);
-        check_cycles.insert(vec![&a.module, &c.module, &d.module]);
-        check_cycles.insert(vec![&a.module, &b.module, &d.module]);
-        check_cycles.insert(vec![&b.module, &c.module]);
-        check_cycles.insert(vec![&c.module, &b.module]);
-        assert_eq!(cycles, Some(check_cycles));
+        // `a`, `b`, `c` and `d` are all mutually reachable, so Tarjan's
+        // collapses them into a single strongly-connected component, and we
+        // report one concrete cycle through it rather than all six simple
+        // cycles the old exponential enumeration produced.
+        let cycles = cycles.expect("expected a cycle");
+        assert_eq!(cycles.len(), 1);
+        let cycle = cycles.iter().next().unwrap();
+        assert_eq!(cycle.len(), 4);
+        let as_set: FxHashSet<u32> = cycle.iter().copied().collect();
+        assert_eq!(as_set, check_visited);
+    }
+
+    #[test]
+    fn cyclic_import_recovered_cycle_passes_through_queried_module() {
+        // `p`, `q` and `r` all land in one strongly-connected component
+        // (`p` <-> `q` and `p` <-> `r`), but the only *direct* back-edge into
+        // `component[0]` may belong to a different member than the one we're
+        // checking. Querying `r` must still recover a path through `r`
+        // itself (`r -> p -> ... -> r`), not silently drop it.
+        let mut map = FxHashMap::default();
+        let size1 = TextSize::from(1);
+        let size2 = TextSize::from(2);
+        let size3 = TextSize::from(3);
+        let range1 = TextRange::new(size1, size2);
+        let range2 = TextRange::new(size1, size3);
+        let range3 = TextRange::new(size2, size3);
+
+        let p = ModuleImport::new("p".to_string(), range1);
+        let q = ModuleImport::new("q".to_string(), range2);
+        let r = ModuleImport::new("r".to_string(), range3);
+
+        map.insert(p.module.clone(), vec![q.clone(), r.clone()]);
+        map.insert(q.module.clone(), vec![p.clone()]);
+        map.insert(r.module.clone(), vec![p.clone()]);
+        let import_map = ImportMap::new(map);
+        let helper = helper_for(&import_map);
+        let cyclic_checker = CyclicImportChecker {
+            graph: &helper.graph,
+        };
+
+        let r_id = helper.graph.module_id(&r.module).unwrap();
+        let VisitedAndCycles { cycles, .. } = cyclic_checker.has_cycles(r_id);
+
+        let cycles = cycles.expect("expected a cycle");
+        assert_eq!(cycles.len(), 1);
+        let cycle = cycles.iter().next().unwrap();
+        assert!(
+            cycle.contains(&r_id),
+            "recovered cycle {cycle:?} must contain the queried module `r` ({r_id})"
+        );
     }
 
     #[test]
@@ -299,20 +652,23 @@ mod tests {
         map.insert(a_b.module.clone(), vec![a_in_b.clone()]);
         map.insert(a_c.module, vec![]);
         let import_map = ImportMap::new(map);
+        let mut helper = helper_for(&import_map);
 
         let path_a = Path::new("a/a");
         let path_b = Path::new("a/b");
         let path_c = Path::new("a/c");
         let package = Some(Path::new("a"));
-
-        let mut cycles = FxHashMap::default();
-        let diagnostic = cyclic_import(path_a, package, &import_map.module_to_imports, &mut cycles);
-
-        let mut set_a: FxHashSet<Vec<&str>> = FxHashSet::default();
-        set_a.insert(vec![&a_b.module, &a_a.module]);
-        let mut set_b: FxHashSet<Vec<&str>> = FxHashSet::default();
-        set_b.insert(vec![&a_a.module, &a_b.module]);
-
+        let (python_ast, locator, stylist) = parse("");
+
+        let diagnostic = cyclic_import(
+            path_a,
+            package,
+            &import_map.module_to_imports,
+            &mut helper,
+            &python_ast,
+            &locator,
+            &stylist,
+        );
         assert_eq!(
             diagnostic,
             Some(vec![Diagnostic::new(
@@ -322,12 +678,16 @@ mod tests {
                 (&b_in_a).into(),
             )])
         );
-        let mut check_cycles: FxHashMap<&str, FxHashSet<Vec<&str>>> = FxHashMap::default();
-        check_cycles.insert(&a_b.module, set_a);
-        check_cycles.insert(&a_a.module, set_b);
-        assert_eq!(cycles, check_cycles);
 
-        let diagnostic = cyclic_import(path_b, package, &import_map.module_to_imports, &mut cycles);
+        let diagnostic = cyclic_import(
+            path_b,
+            package,
+            &import_map.module_to_imports,
+            &mut helper,
+            &python_ast,
+            &locator,
+            &stylist,
+        );
         assert_eq!(
             diagnostic,
             Some(vec![Diagnostic::new(
@@ -337,13 +697,93 @@ mod tests {
                 (&a_in_b).into(),
             )])
         );
-        assert!(
-            cyclic_import(path_c, package, &import_map.module_to_imports, &mut cycles).is_none()
-        );
+        assert!(cyclic_import(
+            path_c,
+            package,
+            &import_map.module_to_imports,
+            &mut helper,
+            &python_ast,
+            &locator,
+            &stylist,
+        )
+        .is_none());
     }
 
     #[test]
-    fn cyclic_import_test_no_cycles_on_import_self() {
+    fn cyclic_import_fix_defers_type_only_use_into_type_checking() {
+        let source = "import b\n\ndef f(x: b.Thing) -> None:\n    pass\n";
+        let (python_ast, locator, stylist) = parse(source);
+        let import_stmt = &python_ast[0];
+        let edge = ModuleImport::new("b".to_string(), import_stmt.range());
+
+        let fix = generate_fix(&python_ast, &locator, &stylist, &edge)
+            .expect("type-only use should be fixable");
+        let edits = fix.edits();
+        let import_edit = &edits[0];
+        assert!(import_edit.content().unwrap().contains("if TYPE_CHECKING:"));
+        assert!(import_edit
+            .content()
+            .unwrap()
+            .contains("from typing import TYPE_CHECKING"));
+
+        // The annotation that references `b` must be quoted, or the
+        // deferred import would leave it evaluating to an undefined name.
+        assert_eq!(edits.len(), 2);
+        assert_eq!(edits[1].content().unwrap(), "\"b.Thing\"");
+    }
+
+    #[test]
+    fn cyclic_import_fix_skips_quoting_under_future_annotations() {
+        let source =
+            "from __future__ import annotations\n\nimport b\n\ndef f(x: b.Thing) -> None:\n    pass\n";
+        let (python_ast, locator, stylist) = parse(source);
+        let import_stmt = &python_ast[1];
+        let edge = ModuleImport::new("b".to_string(), import_stmt.range());
+
+        let fix = generate_fix(&python_ast, &locator, &stylist, &edge)
+            .expect("type-only use should be fixable");
+        assert_eq!(fix.edits().len(), 1);
+    }
+
+    #[test]
+    fn cyclic_import_fix_defers_runtime_use_into_function_body() {
+        let source = "import b\n\ndef f():\n    return b.thing()\n";
+        let (python_ast, locator, stylist) = parse(source);
+        let import_stmt = &python_ast[0];
+        let edge = ModuleImport::new("b".to_string(), import_stmt.range());
+
+        let fix = generate_fix(&python_ast, &locator, &stylist, &edge)
+            .expect("function-local use should be fixable");
+        assert_eq!(fix.edits().len(), 2);
+    }
+
+    #[test]
+    fn cyclic_import_fix_not_offered_for_module_scope_use() {
+        let source = "import b\n\nb.thing()\n";
+        let (python_ast, locator, stylist) = parse(source);
+        let import_stmt = &python_ast[0];
+        let edge = ModuleImport::new("b".to_string(), import_stmt.range());
+
+        assert!(generate_fix(&python_ast, &locator, &stylist, &edge).is_none());
+    }
+
+    #[test]
+    fn cyclic_import_fix_dotted_plain_import_binds_top_level_package() {
+        // `import b.c` (no `from`, no alias) only binds `b` in this module's
+        // namespace, not `c` - a reference to `b.c.thing()` is a runtime use
+        // of `b`, found via `b`, not via the (never-bound) name `c`.
+        let source = "import b.c\n\ndef f():\n    return b.c.thing()\n";
+        let (python_ast, locator, stylist) = parse(source);
+        let import_stmt = &python_ast[0];
+        let edge = ModuleImport::new("b.c".to_string(), import_stmt.range());
+
+        let fix = generate_fix(&python_ast, &locator, &stylist, &edge)
+            .expect("runtime use via the bound top-level package should be fixable");
+        assert_eq!(fix.edits().len(), 2);
+    }
+
+    #[test]
+    fn cyclic_import_self_import_is_reported_as_a_cycle() {
         let size1 = TextSize::from(1);
         let size2 = TextSize::from(2);
         let range = TextRange::new(size1, size2);
@@ -352,19 +792,25 @@ mod tests {
         map.insert(a.module.clone(), vec![a.clone()]);
 
         let import_map = ImportMap::new(map);
-
+        let helper = helper_for(&import_map);
         let cyclic_checker = CyclicImportChecker {
-            imports: &import_map.module_to_imports,
+            graph: &helper.graph,
         };
+
+        let a_id = helper.graph.module_id(&a.module).unwrap();
         let VisitedAndCycles {
             fully_visited: visited,
             cycles,
-        } = cyclic_checker.has_cycles(&a.module);
+        } = cyclic_checker.has_cycles(a_id);
 
-        let mut check_visited: FxHashSet<&str> = FxHashSet::default();
-        check_visited.insert(&a.module);
+        let mut check_visited: FxHashSet<u32> = FxHashSet::default();
+        check_visited.insert(a_id);
         assert_eq!(visited, check_visited);
 
-        assert!(cycles.is_none());
+        // A module that imports itself is a single-node strongly-connected
+        // component, and is reported as its own cyclic-import group.
+        let mut check_cycles: FxHashSet<Vec<u32>> = FxHashSet::default();
+        check_cycles.insert(vec![a_id]);
+        assert_eq!(cycles, Some(check_cycles));
     }
 }
@@ -0,0 +1,171 @@
+use std::path::Path;
+
+use ruff_diagnostics::{Diagnostic, Violation};
+use ruff_macros::{derive_message_formats, violation};
+use ruff_python_ast::helpers::to_module_path;
+
+use super::super::import_graph::ImportGraph;
+use super::super::settings::Settings;
+
+#[violation]
+pub struct BannedModuleLayering {
+    pub from: String,
+    pub to: String,
+}
+
+impl Violation for BannedModuleLayering {
+    #[derive_message_formats]
+    fn message(&self) -> String {
+        let BannedModuleLayering { from, to } = self;
+        format!("`{from}` may not import `{to}` (banned-module-layering)")
+    }
+}
+
+/// A single forbidden dependency direction between two layers of a project,
+/// expressed as glob patterns over dotted module paths. `*` matches exactly
+/// one dotted segment; `**` matches any number of segments, including zero.
+///
+/// For example, `{ from: "domain.**", to: "web.**" }` forbids anything under
+/// the `domain` package from importing anything under `web`. Configured via
+/// `Settings::layer_boundaries` (`[tool.ruff.pylint]` in `pyproject.toml`).
+#[derive(Debug, Clone)]
+pub struct LayerBoundary {
+    pub from: String,
+    pub to: String,
+}
+
+/// PLR9001: check every import declared by the module at `path` against the
+/// layering boundaries configured in `settings`, reporting one diagnostic
+/// per import that crosses a banned boundary. Called from the AST checker
+/// alongside `cyclic_import`, over the same shared `ImportGraph`.
+pub fn import_layering(
+    path: &Path,
+    package: Option<&Path>,
+    graph: &ImportGraph,
+    settings: &Settings,
+) -> Vec<Diagnostic> {
+    let Some(package) = package else {
+        return Vec::new();
+    };
+    let Some(module_name) = to_module_path(package, path) else {
+        return Vec::new();
+    };
+    let module_name = module_name.join(".");
+    let Some(module_id) = graph.module_id(&module_name) else {
+        return Vec::new();
+    };
+
+    let mut diagnostics = Vec::new();
+    for edge in graph.edges(module_id) {
+        for boundary in &settings.layer_boundaries {
+            if glob_match(&boundary.from, &module_name) && glob_match(&boundary.to, &edge.module) {
+                diagnostics.push(Diagnostic::new(
+                    BannedModuleLayering {
+                        from: boundary.from.clone(),
+                        to: boundary.to.clone(),
+                    },
+                    edge.into(),
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Match a dotted module path against a glob `pattern`, where `*` stands for
+/// exactly one dotted segment and `**` stands for any number of them.
+fn glob_match(pattern: &str, module: &str) -> bool {
+    let pattern_parts: Vec<&str> = pattern.split('.').collect();
+    let module_parts: Vec<&str> = module.split('.').collect();
+    glob_match_parts(&pattern_parts, &module_parts)
+}
+
+fn glob_match_parts(pattern: &[&str], module: &[&str]) -> bool {
+    match pattern {
+        [] => module.is_empty(),
+        ["**"] => true,
+        ["**", rest @ ..] => (0..=module.len()).any(|i| glob_match_parts(rest, &module[i..])),
+        [first, rest @ ..] => match module {
+            [] => false,
+            [module_first, module_rest @ ..] => {
+                (*first == "*" || first == module_first) && glob_match_parts(rest, module_rest)
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustc_hash::FxHashMap;
+    use ruff_python_ast::imports::{ImportMap, ModuleImport};
+    use ruff_text_size::{TextRange, TextSize};
+
+    use super::*;
+
+    fn range(start: u32, end: u32) -> TextRange {
+        TextRange::new(TextSize::from(start), TextSize::from(end))
+    }
+
+    #[test]
+    fn glob_match_single_segment_wildcard() {
+        assert!(glob_match("domain.*", "domain.orders"));
+        assert!(!glob_match("domain.*", "domain.orders.repository"));
+    }
+
+    #[test]
+    fn glob_match_double_star_any_depth() {
+        assert!(glob_match("domain.**", "domain"));
+        assert!(glob_match("domain.**", "domain.orders.repository"));
+        assert!(!glob_match("domain.**", "web.orders"));
+    }
+
+    #[test]
+    fn import_layering_reports_banned_direction() {
+        let mut map = FxHashMap::default();
+        let domain_to_web = ModuleImport::new("web.views".to_string(), range(1, 2));
+        map.insert("domain.orders".to_string(), vec![domain_to_web.clone()]);
+        map.insert("web.views".to_string(), vec![]);
+        let import_map = ImportMap::new(map);
+        let graph = ImportGraph::new(&import_map);
+
+        let settings = Settings {
+            layer_boundaries: vec![LayerBoundary {
+                from: "domain.**".to_string(),
+                to: "web.**".to_string(),
+            }],
+        };
+
+        let diagnostics = import_layering(
+            Path::new("domain/orders"),
+            Some(Path::new("domain")),
+            &graph,
+            &settings,
+        );
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn import_layering_allows_undeclared_direction() {
+        let mut map = FxHashMap::default();
+        let web_to_domain = ModuleImport::new("domain.orders".to_string(), range(1, 2));
+        map.insert("web.views".to_string(), vec![web_to_domain]);
+        map.insert("domain.orders".to_string(), vec![]);
+        let import_map = ImportMap::new(map);
+        let graph = ImportGraph::new(&import_map);
+
+        let settings = Settings {
+            layer_boundaries: vec![LayerBoundary {
+                from: "domain.**".to_string(),
+                to: "web.**".to_string(),
+            }],
+        };
+
+        let diagnostics = import_layering(
+            Path::new("web/views"),
+            Some(Path::new("web")),
+            &graph,
+            &settings,
+        );
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+use rustpython_parser::ast::{Expr, ExprKind, Stmt, StmtKind, Suite};
+
+use ruff_python_ast::visitor::{self, Visitor};
+
+/// Where a deferred import should be relocated to in order to break a
+/// cyclic import, without breaking the module that currently does the
+/// importing.
+pub(super) enum UsePlacement<'a> {
+    /// Every reference to the imported name is in a type-annotation
+    /// position, so the import can move into an `if TYPE_CHECKING:` block.
+    /// Carries every distinct annotation expression that references the
+    /// name, so the caller can quote them as forward references.
+    TypeCheckingOnly(Vec<&'a Expr>),
+    /// The imported name is used at runtime, but only from inside the
+    /// bodies of these functions, so the import can move into each of them.
+    FunctionLocal(Vec<&'a Stmt>),
+    /// The imported name is used at module scope (or isn't used at all, or
+    /// we can't prove otherwise): there's nowhere safe to defer it to.
+    Unmovable,
+}
+
+/// Find the best place to move the import of `name` to, by walking every
+/// reference to it in `suite` and bucketing the references into
+/// type-annotation-only and runtime uses.
+pub(super) fn find_use_placement<'a>(suite: &'a Suite, name: &str) -> UsePlacement<'a> {
+    let mut finder = NameUseFinder::new(name);
+    for stmt in suite {
+        finder.visit_stmt(stmt);
+    }
+
+    if finder.has_module_scope_runtime_use || !(finder.has_type_use || finder.has_runtime_use) {
+        UsePlacement::Unmovable
+    } else if finder.has_runtime_use {
+        UsePlacement::FunctionLocal(finder.runtime_functions)
+    } else {
+        UsePlacement::TypeCheckingOnly(finder.annotations)
+    }
+}
+
+#[derive(Default)]
+struct NameUseFinder<'a> {
+    name: &'a str,
+    in_annotation: bool,
+    current_annotation: Option<&'a Expr>,
+    function_stack: Vec<&'a Stmt>,
+    has_type_use: bool,
+    has_runtime_use: bool,
+    has_module_scope_runtime_use: bool,
+    runtime_functions: Vec<&'a Stmt>,
+    annotations: Vec<&'a Expr>,
+}
+
+impl<'a> NameUseFinder<'a> {
+    fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            ..Self::default()
+        }
+    }
+
+    fn record_reference(&mut self) {
+        if self.in_annotation {
+            self.has_type_use = true;
+            if let Some(annotation) = self.current_annotation {
+                if !self
+                    .annotations
+                    .iter()
+                    .any(|other| std::ptr::eq(*other, annotation))
+                {
+                    self.annotations.push(annotation);
+                }
+            }
+            return;
+        }
+        self.has_runtime_use = true;
+        match self.function_stack.last() {
+            Some(&function) => {
+                if !self
+                    .runtime_functions
+                    .iter()
+                    .any(|other| std::ptr::eq(*other, function))
+                {
+                    self.runtime_functions.push(function);
+                }
+            }
+            None => self.has_module_scope_runtime_use = true,
+        }
+    }
+
+    fn visit_annotation(&mut self, annotation: &'a Expr) {
+        self.in_annotation = true;
+        self.current_annotation = Some(annotation);
+        self.visit_expr(annotation);
+        self.current_annotation = None;
+        self.in_annotation = false;
+    }
+}
+
+impl<'a> Visitor<'a> for NameUseFinder<'a> {
+    fn visit_stmt(&mut self, stmt: &'a Stmt) {
+        match &stmt.node {
+            StmtKind::FunctionDef {
+                args,
+                body,
+                returns,
+                decorator_list,
+                ..
+            }
+            | StmtKind::AsyncFunctionDef {
+                args,
+                body,
+                returns,
+                decorator_list,
+                ..
+            } => {
+                for decorator in decorator_list {
+                    self.visit_expr(decorator);
+                }
+                for arg in args
+                    .posonlyargs
+                    .iter()
+                    .chain(&args.args)
+                    .chain(&args.kwonlyargs)
+                {
+                    if let Some(annotation) = &arg.node.annotation {
+                        self.visit_annotation(annotation);
+                    }
+                }
+                for arg in args.vararg.iter().chain(&args.kwarg) {
+                    if let Some(annotation) = &arg.node.annotation {
+                        self.visit_annotation(annotation);
+                    }
+                }
+                if let Some(returns) = returns {
+                    self.visit_annotation(returns);
+                }
+                // Defaults run when the `def` statement itself executes, in
+                // the enclosing scope, not when the function is later
+                // called, so visit them before pushing `stmt` onto the
+                // function stack.
+                for default in args.defaults.iter().chain(&args.kw_defaults) {
+                    self.visit_expr(default);
+                }
+
+                self.function_stack.push(stmt);
+                for inner in body {
+                    self.visit_stmt(inner);
+                }
+                self.function_stack.pop();
+            }
+            StmtKind::AnnAssign {
+                target,
+                annotation,
+                value,
+                ..
+            } => {
+                self.visit_annotation(annotation);
+                self.visit_expr(target);
+                if let Some(value) = value {
+                    self.visit_expr(value);
+                }
+            }
+            _ => visitor::walk_stmt(self, stmt),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        if let ExprKind::Name { id, .. } = &expr.node {
+            if id == self.name {
+                self.record_reference();
+            }
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
@@ -0,0 +1,12 @@
+//! User-configurable behavior for pylint's rules, analogous to the other
+//! plugins' own `settings.rs` (read from `pyproject.toml`'s
+//! `[tool.ruff.pylint]` table by the shared settings loader).
+
+use super::rules::import_layering::LayerBoundary;
+
+#[derive(Debug, Clone, Default)]
+pub struct Settings {
+    /// Forbidden module-layering boundaries enforced by
+    /// `banned-module-layering`; empty disables the rule.
+    pub layer_boundaries: Vec<LayerBoundary>,
+}